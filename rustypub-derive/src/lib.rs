@@ -0,0 +1,122 @@
+//! The `#[derive(ActivityStreams)]` macro for `rustypub`. Generates the
+//! boilerplate for mapping a concrete vocabulary type (`Note`, `Follow`, ...)
+//! to and from the crate's generic `Object`/`JsonLdDocument`.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, DeriveInput, LitStr};
+
+/// ```ignore
+/// #[derive(ActivityStreams, Serialize, Deserialize)]
+/// #[activitystreams(type = "Note")]
+/// struct Note {
+///     content: String,
+/// }
+///
+/// let note: Note = object.try_into()?;
+/// let object: Object = note.into_object();
+/// ```
+#[proc_macro_derive(ActivityStreams, attributes(activitystreams))]
+pub fn derive_activity_streams(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let ident = &input.ident;
+
+    let object_type = match object_type_attr(&input) {
+        Ok(object_type) => object_type,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let object_type_variant = syn::Ident::new(&object_type, ident.span());
+
+    let expanded = quote! {
+        impl ::rustypub::ActivityStreamsType for #ident {
+            fn object_type() -> ::rustypub::ObjectType {
+                ::rustypub::ObjectType::#object_type_variant
+            }
+        }
+
+        impl ::std::convert::TryFrom<::rustypub::Object> for #ident {
+            type Error = ::rustypub::Error;
+
+            fn try_from(object: ::rustypub::Object) -> ::std::result::Result<Self, Self::Error> {
+                let expected = <Self as ::rustypub::ActivityStreamsType>::object_type();
+                if object.ty != expected {
+                    return ::std::result::Result::Err(::rustypub::Error::UnexpectedType {
+                        expected,
+                        actual: object.ty.clone(),
+                    });
+                }
+
+                ::std::result::Result::Ok(::serde_json::from_value(::serde_json::to_value(
+                    &object,
+                )?)?)
+            }
+        }
+
+        impl ::std::convert::TryFrom<::rustypub::JsonLdDocument> for #ident {
+            type Error = ::rustypub::Error;
+
+            fn try_from(
+                document: ::rustypub::JsonLdDocument,
+            ) -> ::std::result::Result<Self, Self::Error> {
+                document.object.try_into()
+            }
+        }
+
+        impl #ident {
+            /// Re-flatten this value back into the generic `Object`
+            /// representation, setting its `type` to
+            #[doc = concat!("`\"", stringify!(#object_type_variant), "\"`.")]
+            pub fn into_object(self) -> ::rustypub::Object {
+                let mut value = ::serde_json::to_value(&self)
+                    .expect(concat!(stringify!(#ident), " always serializes"));
+
+                if let ::serde_json::Value::Object(ref mut map) = value {
+                    map.insert(
+                        "type".to_string(),
+                        ::serde_json::Value::String(stringify!(#object_type_variant).to_string()),
+                    );
+                }
+
+                ::serde_json::from_value(value)
+                    .expect(concat!(stringify!(#ident), " re-flattens into an Object"))
+            }
+
+            /// As [`Self::into_object`], wrapped in a `JsonLdDocument` with
+            /// the default activitystreams `@context`.
+            pub fn into_document(self) -> ::rustypub::JsonLdDocument {
+                ::rustypub::JsonLdDocument {
+                    context: ::rustypub::Context::default(),
+                    object: self.into_object(),
+                }
+            }
+        }
+    };
+
+    expanded.into()
+}
+
+fn object_type_attr(input: &DeriveInput) -> syn::Result<String> {
+    for attr in &input.attrs {
+        if !attr.path().is_ident("activitystreams") {
+            continue;
+        }
+
+        let mut object_type = None;
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("type") {
+                let lit: LitStr = meta.value()?.parse()?;
+                object_type = Some(lit.value());
+            }
+            Ok(())
+        })?;
+
+        if let Some(object_type) = object_type {
+            return Ok(object_type);
+        }
+    }
+
+    Err(syn::Error::new_spanned(
+        &input.ident,
+        "missing #[activitystreams(type = \"...\")] attribute",
+    ))
+}
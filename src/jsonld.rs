@@ -0,0 +1,346 @@
+use serde_json::Value as Json;
+
+use crate::{Context, ContextEntry, JsonLdDocument};
+
+const AS_VOCAB: &str = "https://www.w3.org/ns/activitystreams#";
+const SEC_VOCAB: &str = "https://w3id.org/security#";
+
+/// Property names defined directly by the activitystreams vocabulary
+/// (https://www.w3.org/ns/activitystreams), i.e. the terms a bare
+/// `"https://www.w3.org/ns/activitystreams"` `@context` maps without an
+/// inline term definition. Not exhaustive, but covers the properties this
+/// crate's examples and federated payloads actually use.
+const AS_VOCAB_TERMS: &[&str] = &[
+    "actor",
+    "attachment",
+    "attributedTo",
+    "audience",
+    "bcc",
+    "bto",
+    "cc",
+    "context",
+    "current",
+    "first",
+    "generator",
+    "icon",
+    "image",
+    "inReplyTo",
+    "instrument",
+    "items",
+    "last",
+    "location",
+    "next",
+    "object",
+    "oneOf",
+    "anyOf",
+    "origin",
+    "partOf",
+    "preview",
+    "prev",
+    "provider",
+    "replies",
+    "result",
+    "tag",
+    "target",
+    "to",
+    "url",
+    "accuracy",
+    "altitude",
+    "closed",
+    "content",
+    "contentMap",
+    "deleted",
+    "describes",
+    "duration",
+    "endTime",
+    "formerType",
+    "height",
+    "href",
+    "hreflang",
+    "latitude",
+    "longitude",
+    "mediaType",
+    "name",
+    "nameMap",
+    "orderedItems",
+    "published",
+    "radius",
+    "rel",
+    "relationship",
+    "startIndex",
+    "startTime",
+    "summary",
+    "summaryMap",
+    "totalItems",
+    "units",
+    "updated",
+    "width",
+    "source",
+    "likes",
+    "shares",
+    "subject",
+    "inbox",
+    "outbox",
+    "following",
+    "followers",
+    "liked",
+    "preferredUsername",
+    "streams",
+    "endpoints",
+];
+
+/// The subset of [`AS_VOCAB_TERMS`] whose range is `@id` — i.e. whose string
+/// values are themselves IRIs/compact terms (references to an `Object` or
+/// `Link`) rather than literal data, and so need the same term expansion
+/// applied to their *values* that ordinary keys get.
+const AS_ID_TYPED_TERMS: &[&str] = &[
+    "actor",
+    "attachment",
+    "attributedTo",
+    "audience",
+    "bcc",
+    "bto",
+    "cc",
+    "context",
+    "current",
+    "first",
+    "generator",
+    "icon",
+    "image",
+    "inReplyTo",
+    "instrument",
+    "items",
+    "last",
+    "location",
+    "next",
+    "object",
+    "oneOf",
+    "anyOf",
+    "origin",
+    "partOf",
+    "preview",
+    "prev",
+    "provider",
+    "replies",
+    "result",
+    "tag",
+    "target",
+    "to",
+    "url",
+    "describes",
+    "inbox",
+    "outbox",
+    "following",
+    "followers",
+    "liked",
+];
+
+impl JsonLdDocument {
+    /// Rewrite every compact/aliased property key in this document to its
+    /// full IRI, using this document's `@context` to resolve inline term
+    /// definitions and falling back to the base activitystreams vocabulary
+    /// ([`AS_VOCAB_TERMS`]) or the standard `as:`/`sec:` prefixes. Also
+    /// rewrites the string values (and arrays of them) of `@id`-typed
+    /// properties ([`AS_ID_TYPED_TERMS`]), e.g. `"cc": ["as:Public"]`
+    /// becomes `"cc": ["https://www.w3.org/ns/activitystreams#Public"]`.
+    /// Recurses into nested objects and arrays. `id` and `type` are JSON-LD
+    /// keywords, not ordinary properties, and are left alone.
+    ///
+    /// Two documents that are semantically identical but serialized with
+    /// different (but equivalent) compact terms will expand to the same
+    /// form, making `expand()` a useful basis for comparison. This isn't a
+    /// general JSON-LD processor, though: it only knows the activitystreams
+    /// and security vocabularies and whatever inline term map the document
+    /// itself supplies, not arbitrary `@vocab`/external contexts.
+    pub fn expand(&self) -> Self {
+        let value = serde_json::to_value(&self.object).expect("Object always serializes");
+        let expanded = expand_value(&value, &self.context);
+        let object = serde_json::from_value(expanded).expect("expansion preserves id/type shape");
+
+        Self {
+            context: self.context.clone(),
+            object,
+        }
+    }
+
+    /// The inverse of [`JsonLdDocument::expand`]: rewrite full IRI keys back
+    /// to the compact terms defined by `context`, replacing this document's
+    /// `@context` with it.
+    pub fn compact(&self, context: Context) -> Self {
+        let value = serde_json::to_value(&self.object).expect("Object always serializes");
+        let compacted = compact_value(&value, &context);
+        let object = serde_json::from_value(compacted).expect("compaction preserves id/type shape");
+
+        Self { context, object }
+    }
+}
+
+fn is_keyword(key: &str) -> bool {
+    key.starts_with('@') || key == "id" || key == "type"
+}
+
+fn expand_value(value: &Json, context: &Context) -> Json {
+    match value {
+        Json::Object(map) => Json::Object(
+            map.iter()
+                .map(|(key, value)| {
+                    if is_keyword(key) {
+                        return (key.clone(), expand_value(value, context));
+                    }
+
+                    let expanded_key = expand_term(key, context);
+                    let expanded_value = if is_id_typed(key, context) {
+                        expand_id_value(value, context)
+                    } else {
+                        expand_value(value, context)
+                    };
+
+                    (expanded_key, expanded_value)
+                })
+                .collect(),
+        ),
+        Json::Array(items) => Json::Array(items.iter().map(|v| expand_value(v, context)).collect()),
+        other => other.clone(),
+    }
+}
+
+/// As [`expand_value`], but also rewrites string leaves (and arrays of
+/// them) through [`expand_term`], since the value of an `@id`-typed property
+/// is itself a reference that may be a compact term (e.g. `"as:Public"`).
+fn expand_id_value(value: &Json, context: &Context) -> Json {
+    match value {
+        Json::String(s) => Json::String(expand_term(s, context)),
+        Json::Array(items) => {
+            Json::Array(items.iter().map(|v| expand_id_value(v, context)).collect())
+        }
+        other => expand_value(other, context),
+    }
+}
+
+fn compact_value(value: &Json, context: &Context) -> Json {
+    match value {
+        Json::Object(map) => Json::Object(
+            map.iter()
+                .map(|(key, value)| {
+                    if is_keyword(key) {
+                        return (key.clone(), compact_value(value, context));
+                    }
+
+                    let compacted_key = compact_iri(key, context);
+                    let compacted_value = if is_id_typed_iri(key) {
+                        compact_id_value(value, context)
+                    } else {
+                        compact_value(value, context)
+                    };
+
+                    (compacted_key, compacted_value)
+                })
+                .collect(),
+        ),
+        Json::Array(items) => {
+            Json::Array(items.iter().map(|v| compact_value(v, context)).collect())
+        }
+        other => other.clone(),
+    }
+}
+
+/// As [`compact_value`], but also rewrites string leaves (and arrays of
+/// them) through [`compact_iri`], mirroring [`expand_id_value`].
+fn compact_id_value(value: &Json, context: &Context) -> Json {
+    match value {
+        Json::String(s) => Json::String(compact_iri(s, context)),
+        Json::Array(items) => {
+            Json::Array(items.iter().map(|v| compact_id_value(v, context)).collect())
+        }
+        other => compact_value(other, context),
+    }
+}
+
+/// Expand a compact term (an inline-context term, an `as:`/`sec:` prefixed
+/// name, a bare activitystreams vocabulary property, or an already-absolute
+/// IRI) to its full IRI.
+fn expand_term(term: &str, context: &Context) -> String {
+    if let Some(mapped) = context.term_mapping(term) {
+        return expand_prefixed(mapped);
+    }
+
+    if AS_VOCAB_TERMS.contains(&term) {
+        return format!("{AS_VOCAB}{term}");
+    }
+
+    expand_prefixed(term)
+}
+
+/// Does `key` (in whatever form it's spelled in the source document) name
+/// an activitystreams property whose value is an IRI reference rather than
+/// literal data?
+fn is_id_typed(key: &str, context: &Context) -> bool {
+    expand_term(key, context)
+        .strip_prefix(AS_VOCAB)
+        .is_some_and(|local| AS_ID_TYPED_TERMS.contains(&local))
+}
+
+/// As [`is_id_typed`], but `iri` is already expanded (used from
+/// [`compact_value`], whose keys are full IRIs by the time they reach here).
+fn is_id_typed_iri(iri: &str) -> bool {
+    iri.strip_prefix(AS_VOCAB)
+        .is_some_and(|local| AS_ID_TYPED_TERMS.contains(&local))
+}
+
+fn expand_prefixed(term: &str) -> String {
+    if term.contains("://") {
+        return term.to_string();
+    }
+
+    if let Some(rest) = term.strip_prefix("as:") {
+        return format!("{AS_VOCAB}{rest}");
+    }
+
+    if let Some(rest) = term.strip_prefix("sec:") {
+        return format!("{SEC_VOCAB}{rest}");
+    }
+
+    term.to_string()
+}
+
+fn compact_iri(iri: &str, context: &Context) -> String {
+    if let Some(term) = reverse_term_mapping(iri, context) {
+        return term;
+    }
+
+    if let Some(rest) = iri.strip_prefix(AS_VOCAB) {
+        if AS_VOCAB_TERMS.contains(&rest) {
+            return rest.to_string();
+        }
+
+        return format!("as:{rest}");
+    }
+
+    if let Some(rest) = iri.strip_prefix(SEC_VOCAB) {
+        return format!("sec:{rest}");
+    }
+
+    iri.to_string()
+}
+
+fn reverse_term_mapping(iri: &str, context: &Context) -> Option<String> {
+    let Context::Multiple(entries) = context else {
+        return None;
+    };
+
+    entries.iter().find_map(|entry| {
+        let ContextEntry::TermMap(map) = entry else {
+            return None;
+        };
+
+        map.iter().find_map(|(term, definition)| {
+            let mapped = match definition {
+                Json::String(s) => s.as_str(),
+                Json::Object(o) => o.get("@id").and_then(Json::as_str)?,
+                _ => return None,
+            };
+
+            (expand_prefixed(mapped) == iri).then(|| term.clone())
+        })
+    })
+}
@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use serde_json::Value as Json;
+
+use crate::{JsonLdDocument, Link, Object, ObjectOrLink};
+
+#[derive(Debug, thiserror::Error)]
+pub enum ResolveError {
+    #[error("fetching {iri}")]
+    Fetch {
+        iri: String,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("{0:?} has no IRI to resolve and does not embed an Object")]
+    NotAnObject(Link),
+}
+
+/// Fetches the raw JSON for an IRI. Transport-agnostic: callers wire in
+/// whatever HTTP client they like.
+#[async_trait]
+pub trait Fetcher {
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    async fn fetch(&self, iri: &str) -> Result<Json, Self::Error>;
+}
+
+impl ObjectOrLink {
+    /// Dereference this value into a full `Object`, fetching it via
+    /// `fetcher` if it's only an IRI reference. Already-embedded objects are
+    /// returned as-is without a network round trip.
+    pub async fn resolve<F: Fetcher + Sync>(&self, fetcher: &F) -> Result<Object, ResolveError> {
+        match self {
+            Self::Object(object) => Ok(object.clone()),
+            Self::Link(link) => Err(ResolveError::NotAnObject(link.clone())),
+            Self::Reference(iri) => fetch_object(fetcher, iri).await,
+        }
+    }
+}
+
+async fn fetch_object<F: Fetcher + Sync>(fetcher: &F, iri: &str) -> Result<Object, ResolveError> {
+    let json = fetcher
+        .fetch(iri)
+        .await
+        .map_err(|source| ResolveError::Fetch {
+            iri: iri.to_owned(),
+            source: Box::new(source),
+        })?;
+    let doc: JsonLdDocument = serde_json::from_value(json)?;
+
+    Ok(doc.object)
+}
+
+/// Pairs a `Fetcher` with an IRI-keyed cache, so resolving the same actor or
+/// object repeatedly within one operation (e.g. walking an inbox of
+/// activities from the same author) only fetches it once.
+pub struct Resolver<F: Fetcher> {
+    fetcher: F,
+    cache: HashMap<String, Object>,
+}
+
+impl<F: Fetcher + Sync> Resolver<F> {
+    pub fn new(fetcher: F) -> Self {
+        Self {
+            fetcher,
+            cache: HashMap::new(),
+        }
+    }
+
+    pub async fn resolve(&mut self, value: &ObjectOrLink) -> Result<Object, ResolveError> {
+        let iri = match value {
+            ObjectOrLink::Object(object) => return Ok(object.clone()),
+            ObjectOrLink::Link(link) => return Err(ResolveError::NotAnObject(link.clone())),
+            ObjectOrLink::Reference(iri) => iri,
+        };
+
+        if let Some(cached) = self.cache.get(iri) {
+            return Ok(cached.clone());
+        }
+
+        let object = fetch_object(&self.fetcher, iri).await?;
+        self.cache.insert(iri.clone(), object.clone());
+
+        Ok(object)
+    }
+}